@@ -0,0 +1,61 @@
+use std::sync::Arc;
+
+use crate::audio::mixer::{AudioMixer, AudioSource, SoundHandle};
+
+/// Decouples asset loading and playback control from the mixing pipeline:
+/// register a sample buffer once to get a handle, then trigger, reposition
+/// and stop it by that handle from anywhere (input handling, proximity
+/// checks, scripted events) without touching the mixer directly.
+pub trait AudioBackend {
+    /// Registers a mono sample buffer as a playable sound and returns a
+    /// handle to it. The sound starts out stopped.
+    fn register_sound(&self, samples: &[f32]) -> SoundHandle;
+    /// Registers a sound that plays `intro` once then loops `loop_segment`
+    /// forever, e.g. background ambience decoded via
+    /// [`crate::audio::decode`]. The sound starts out stopped.
+    fn register_looping_sound(&self, intro: &[f32], loop_segment: &[f32]) -> SoundHandle;
+    /// Starts (or resumes) playback of a registered sound.
+    fn play_sound(&self, handle: SoundHandle);
+    /// Updates the listener-relative position used for HRTF spatialization.
+    fn set_source_position(&self, handle: SoundHandle, position: hrtf::Vec3);
+    /// Stops playback without discarding the sound, so it can be resumed.
+    fn stop(&self, handle: SoundHandle);
+    /// Advances playback by one chunk, returning the mixed interleaved
+    /// stereo buffer for that chunk.
+    fn tick(&self) -> Vec<f32>;
+}
+
+impl AudioBackend for AudioMixer {
+    fn register_sound(&self, samples: &[f32]) -> SoundHandle {
+        self.add_source(Arc::new(AudioSource::new(samples.to_vec())))
+    }
+
+    fn register_looping_sound(&self, intro: &[f32], loop_segment: &[f32]) -> SoundHandle {
+        self.add_source(Arc::new(AudioSource::start_multi(
+            intro.to_vec(),
+            loop_segment.to_vec(),
+        )))
+    }
+
+    fn play_sound(&self, handle: SoundHandle) {
+        if let Some(source) = self.get_source(handle) {
+            source.set_playing(true);
+        }
+    }
+
+    fn set_source_position(&self, handle: SoundHandle, position: hrtf::Vec3) {
+        if let Some(source) = self.get_source(handle) {
+            source.set_position(position);
+        }
+    }
+
+    fn stop(&self, handle: SoundHandle) {
+        if let Some(source) = self.get_source(handle) {
+            source.set_playing(false);
+        }
+    }
+
+    fn tick(&self) -> Vec<f32> {
+        self.mix_chunk()
+    }
+}
@@ -0,0 +1,58 @@
+use std::collections::VecDeque;
+use std::sync::Mutex;
+
+/// A monotonically increasing timestamp expressed in samples, used to keep
+/// independently produced audio buffers in sync with each other and with the
+/// output device's own clock.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Clock(pub u64);
+
+impl Clock {
+    pub fn zero() -> Self {
+        Clock(0)
+    }
+
+    pub fn advance(self, samples: u64) -> Self {
+        Clock(self.0 + samples)
+    }
+}
+
+/// A queue of `T` values tagged with a `Clock`, kept in clock order so that
+/// frames produced by independent sources can be drained in sync rather than
+/// in whatever order they happened to be pushed.
+pub struct ClockedQueue<T> {
+    items: Mutex<VecDeque<(Clock, T)>>,
+}
+
+impl<T> ClockedQueue<T> {
+    pub fn new() -> Self {
+        Self {
+            items: Mutex::new(VecDeque::new()),
+        }
+    }
+
+    /// Pushes a value, keeping the queue ordered by clock.
+    pub fn push(&self, clock: Clock, value: T) {
+        let mut items = self.items.lock().unwrap();
+        let pos = items.partition_point(|(c, _)| *c <= clock);
+        items.insert(pos, (clock, value));
+    }
+
+    /// Removes and returns the earliest (lowest-clock) item, if any.
+    pub fn pop_next(&self) -> Option<(Clock, T)> {
+        self.items.lock().unwrap().pop_front()
+    }
+
+    /// Pushes a value that was just popped back onto the front of the queue,
+    /// for when a consumer finds it isn't due yet and wants to wait rather
+    /// than play it early.
+    pub fn unpop(&self, clock: Clock, value: T) {
+        self.items.lock().unwrap().push_front((clock, value));
+    }
+}
+
+impl<T> Default for ClockedQueue<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,217 @@
+use std::collections::VecDeque;
+use std::fs::File;
+use std::io::{BufReader, Read};
+use std::path::Path;
+
+use anyhow::{anyhow, Result};
+
+/// Mono samples decoded per streaming chunk. The chunks are appended into
+/// one contiguous buffer that is returned once the whole asset is decoded.
+const DECODE_CHUNK_SAMPLES: usize = 4096;
+
+/// A codec-specific mono PCM stream, decoded incrementally rather than all
+/// at once.
+trait Decoder {
+    /// Native sample rate of the underlying stream.
+    fn sample_rate(&self) -> u32;
+    /// Decodes up to `max_samples` further mono samples into `out`,
+    /// returning how many were appended. Returns 0 once the stream is
+    /// exhausted.
+    fn decode_chunk(&mut self, max_samples: usize, out: &mut Vec<f32>) -> usize;
+}
+
+struct WavDecoder {
+    reader: hound::WavReader<BufReader<File>>,
+}
+
+impl WavDecoder {
+    fn open(path: &Path) -> Result<Self> {
+        Ok(Self {
+            reader: hound::WavReader::open(path)?,
+        })
+    }
+}
+
+impl Decoder for WavDecoder {
+    fn sample_rate(&self) -> u32 {
+        self.reader.spec().sample_rate
+    }
+
+    fn decode_chunk(&mut self, max_samples: usize, out: &mut Vec<f32>) -> usize {
+        let spec = self.reader.spec();
+        let channels = spec.channels.max(1) as usize;
+        let raw: Vec<f32> = match spec.sample_format {
+            hound::SampleFormat::Float => self
+                .reader
+                .samples::<f32>()
+                .take(max_samples * channels)
+                .map_while(Result::ok)
+                .collect(),
+            hound::SampleFormat::Int => self
+                .reader
+                .samples::<i16>()
+                .take(max_samples * channels)
+                .map_while(Result::ok)
+                .map(|s| s as f32 / i16::MAX as f32)
+                .collect(),
+        };
+        let before = out.len();
+        out.extend(raw.chunks_exact(channels).map(|frame| frame.iter().sum::<f32>() / channels as f32));
+        out.len() - before
+    }
+}
+
+struct OggDecoder {
+    reader: lewton::inside_ogg::OggStreamReader<BufReader<File>>,
+    pending: VecDeque<f32>,
+}
+
+impl OggDecoder {
+    fn open(path: &Path) -> Result<Self> {
+        let file = BufReader::new(File::open(path)?);
+        let reader = lewton::inside_ogg::OggStreamReader::new(file)
+            .map_err(|e| anyhow!("failed to open OGG stream {:?}: {:?}", path, e))?;
+        Ok(Self {
+            reader,
+            pending: VecDeque::new(),
+        })
+    }
+
+    fn mix_packet_to_mono(packet: Vec<Vec<i16>>) -> impl Iterator<Item = f32> {
+        let channels = packet.len().max(1) as f32;
+        let len = packet.first().map_or(0, Vec::len);
+        (0..len).map(move |i| packet.iter().map(|c| c[i] as f32 / i16::MAX as f32).sum::<f32>() / channels)
+    }
+}
+
+impl Decoder for OggDecoder {
+    fn sample_rate(&self) -> u32 {
+        self.reader.ident_hdr.audio_sample_rate
+    }
+
+    fn decode_chunk(&mut self, max_samples: usize, out: &mut Vec<f32>) -> usize {
+        let mut produced = 0;
+        while produced < max_samples {
+            if let Some(sample) = self.pending.pop_front() {
+                out.push(sample);
+                produced += 1;
+                continue;
+            }
+            match self.reader.read_dec_packet() {
+                Ok(Some(packet)) => self.pending.extend(Self::mix_packet_to_mono(packet)),
+                _ => break,
+            }
+        }
+        produced
+    }
+}
+
+struct Mp3Decoder {
+    decoder: puremp3::Mp3Decoder<BufReader<File>>,
+    sample_rate: u32,
+    pending: VecDeque<f32>,
+}
+
+impl Mp3Decoder {
+    fn open(path: &Path) -> Result<Self> {
+        let file = BufReader::new(File::open(path)?);
+        let mut decoder = puremp3::Mp3Decoder::new(file);
+        let first_frame = decoder
+            .next_frame()
+            .map_err(|e| anyhow!("failed to decode MP3 {:?}: {:?}", path, e))?;
+        let sample_rate = first_frame.sample_rate.hz();
+        let mut pending = VecDeque::new();
+        pending.extend(Self::mix_frame_to_mono(&first_frame));
+        Ok(Self {
+            decoder,
+            sample_rate,
+            pending,
+        })
+    }
+
+    fn mix_frame_to_mono(frame: &puremp3::Frame) -> Vec<f32> {
+        frame.samples[0]
+            .iter()
+            .zip(frame.samples[1].iter())
+            .map(|(&l, &r)| (l + r) / 2.0)
+            .collect()
+    }
+}
+
+impl Decoder for Mp3Decoder {
+    fn sample_rate(&self) -> u32 {
+        self.sample_rate
+    }
+
+    fn decode_chunk(&mut self, max_samples: usize, out: &mut Vec<f32>) -> usize {
+        let mut produced = 0;
+        while produced < max_samples {
+            if let Some(sample) = self.pending.pop_front() {
+                out.push(sample);
+                produced += 1;
+                continue;
+            }
+            match self.decoder.next_frame() {
+                Ok(frame) => self.pending.extend(Self::mix_frame_to_mono(&frame)),
+                Err(_) => break,
+            }
+        }
+        produced
+    }
+}
+
+enum AssetFormat {
+    Wav,
+    Ogg,
+    Mp3,
+}
+
+/// Picks a codec by extension first, falling back to magic bytes so renamed
+/// or extension-less assets still load.
+fn sniff_format(path: &Path) -> Result<AssetFormat> {
+    if let Some(ext) = path.extension().and_then(|e| e.to_str()) {
+        match ext.to_ascii_lowercase().as_str() {
+            "wav" => return Ok(AssetFormat::Wav),
+            "ogg" => return Ok(AssetFormat::Ogg),
+            "mp3" => return Ok(AssetFormat::Mp3),
+            _ => {}
+        }
+    }
+
+    let mut magic = [0u8; 3];
+    File::open(path)?.read_exact(&mut magic)?;
+    if &magic == b"RIF" {
+        Ok(AssetFormat::Wav)
+    } else if &magic == b"Ogg" {
+        Ok(AssetFormat::Ogg)
+    } else if &magic == b"ID3" || (magic[0] == 0xFF && magic[1] & 0xE0 == 0xE0) {
+        Ok(AssetFormat::Mp3)
+    } else {
+        Err(anyhow!("unrecognized audio format for {:?}", path))
+    }
+}
+
+fn open_decoder(path: &Path) -> Result<Box<dyn Decoder>> {
+    match sniff_format(path)? {
+        AssetFormat::Wav => Ok(Box::new(WavDecoder::open(path)?)),
+        AssetFormat::Ogg => Ok(Box::new(OggDecoder::open(path)?)),
+        AssetFormat::Mp3 => Ok(Box::new(Mp3Decoder::open(path)?)),
+    }
+}
+
+/// Decodes an asset (WAV, OGG Vorbis, or MP3, picked by extension with a
+/// magic-byte fallback) into a mono `f32` buffer and its native sample rate.
+/// Decoding happens in small streaming chunks internally, but this call
+/// still only returns once the whole asset is decoded — callers that want
+/// startup to stay unblocked on large assets should run it on a background
+/// thread (as `setup_audio` in `main.rs` does) rather than calling it inline.
+pub fn load_mono_samples(path: impl AsRef<Path>) -> Result<(Vec<f32>, u32)> {
+    let path = path.as_ref();
+    let mut decoder = open_decoder(path)?;
+    let sample_rate = decoder.sample_rate();
+
+    let mut samples = Vec::new();
+    while decoder.decode_chunk(DECODE_CHUNK_SAMPLES, &mut samples) > 0 {}
+
+    Ok((samples, sample_rate))
+}
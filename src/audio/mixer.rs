@@ -0,0 +1,236 @@
+use std::sync::{Arc, Mutex};
+
+use generational_arena::Arena;
+
+use crate::filter::AudioBandProcessor;
+
+/// A chunk of interleaved stereo samples produced by a source for one tick of
+/// the mixer.
+pub type AudioFrame = Vec<f32>;
+
+/// Opaque handle to a sound registered with an [`AudioMixer`], returned by
+/// [`AudioMixer::add_source`] and used to control playback afterwards.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SoundHandle(generational_arena::Index);
+
+/// Reference distance (scene units) at which distance gain is unity.
+const REFERENCE_DISTANCE: f32 = 1.0;
+
+/// Per-band air-absorption coefficients (low → high band), tuned so high
+/// frequencies attenuate noticeably faster than low ones as distance grows.
+const BAND_ABSORPTION: [f32; 5] = [0.0005, 0.001, 0.002, 0.004, 0.008];
+
+/// Inverse-distance attenuation: unity at (and within) `REFERENCE_DISTANCE`,
+/// falling off as the source moves further from the listener.
+fn distance_gain(distance: f32) -> f32 {
+    REFERENCE_DISTANCE / distance.max(REFERENCE_DISTANCE)
+}
+
+/// Derives the 5 filter band gains from distance using a simple exponential
+/// air-absorption model (`gain = e^(-coefficient * distance)` per band), so
+/// higher bands roll off faster than lower ones as distance grows.
+fn air_absorption_bands(distance: f32) -> [f32; 5] {
+    let mut bands = [1.0; 5];
+    for (gain, coefficient) in bands.iter_mut().zip(BAND_ABSORPTION.iter()) {
+        *gain = (-coefficient * distance.max(0.0)).exp();
+    }
+    bands
+}
+
+/// Per-source state carried between ticks: the band filter (which has
+/// internal history) and the HRTF crossfade history for this source alone.
+struct SourceDsp {
+    filter: AudioBandProcessor,
+    prev_left_samples: Vec<f32>,
+    prev_right_samples: Vec<f32>,
+    prev_sample_vector: hrtf::Vec3,
+    prev_distance_gain: f32,
+}
+
+/// An independently playing sound within the scene: its own sample buffer and
+/// playback cursor, a position in listener space, and the DSP state carried
+/// between ticks.
+pub struct AudioSource {
+    samples: Vec<f32>,
+    /// Index the playback cursor wraps back to on reaching the end. `0` for
+    /// a plain loop; past the intro length for an intro+loop source.
+    loop_start: usize,
+    cursor: Mutex<usize>,
+    position: Mutex<hrtf::Vec3>,
+    playing: Mutex<bool>,
+    dsp: Mutex<SourceDsp>,
+}
+
+impl AudioSource {
+    pub fn new(samples: Vec<f32>) -> Self {
+        Self::from_parts(samples, 0)
+    }
+
+    /// Builds a source that plays `intro` once, then seamlessly loops
+    /// `loop_segment` forever — useful for background ambience with a
+    /// distinct wind-up and a repeating body. An empty `loop_segment` falls
+    /// back to looping the whole buffer from the start, rather than wrapping
+    /// the cursor to an out-of-bounds index past the intro.
+    pub fn start_multi(intro: Vec<f32>, loop_segment: Vec<f32>) -> Self {
+        if loop_segment.is_empty() {
+            return Self::from_parts(intro, 0);
+        }
+        let loop_start = intro.len();
+        let mut samples = intro;
+        samples.extend(loop_segment);
+        Self::from_parts(samples, loop_start)
+    }
+
+    fn from_parts(samples: Vec<f32>, loop_start: usize) -> Self {
+        // A loop start at or past the end of the buffer would make the
+        // cursor wrap to an out-of-bounds index; fall back to looping from
+        // the start instead.
+        let loop_start = if loop_start < samples.len() {
+            loop_start
+        } else {
+            0
+        };
+        Self {
+            samples,
+            loop_start,
+            cursor: Mutex::new(0),
+            position: Mutex::new(hrtf::Vec3::new(0.0, 0.0, 1.0)),
+            playing: Mutex::new(false),
+            dsp: Mutex::new(SourceDsp {
+                filter: AudioBandProcessor::new(),
+                prev_left_samples: Vec::new(),
+                prev_right_samples: Vec::new(),
+                prev_sample_vector: hrtf::Vec3::new(0.0, 0.0, 1.0),
+                prev_distance_gain: 1.0,
+            }),
+        }
+    }
+
+    /// Updates this source's position relative to the listener.
+    pub fn set_position(&self, position: hrtf::Vec3) {
+        *self.position.lock().unwrap() = position;
+    }
+
+    fn position(&self) -> hrtf::Vec3 {
+        *self.position.lock().unwrap()
+    }
+
+    /// Starts or stops playback. A stopped source produces no frames but
+    /// keeps its place in the mixer, so it can be resumed by handle later.
+    pub fn set_playing(&self, playing: bool) {
+        *self.playing.lock().unwrap() = playing;
+    }
+
+    pub fn is_playing(&self) -> bool {
+        *self.playing.lock().unwrap()
+    }
+
+    /// Pulls the next `len` mono samples for this source, wrapping back to
+    /// `loop_start` once the buffer is exhausted.
+    fn next_mono_chunk(&self, len: usize) -> Vec<f32> {
+        if self.samples.is_empty() {
+            return vec![0.0; len];
+        }
+        let mut cursor = self.cursor.lock().unwrap();
+        let mut chunk = Vec::with_capacity(len);
+        for _ in 0..len {
+            chunk.push(self.samples[*cursor]);
+            *cursor += 1;
+            if *cursor >= self.samples.len() {
+                *cursor = self.loop_start;
+            }
+        }
+        chunk
+    }
+
+    /// Runs this source's band filter and HRTF spatialization over the next
+    /// `len` mono samples and returns the resulting stereo frame. Distance to
+    /// the listener (the length of `position`) drives both the HRTF's overall
+    /// distance gain and per-band air absorption, so sounds get quieter and
+    /// duller the further away they are.
+    pub fn produce_chunk(&self, processor: &mut hrtf::HrtfProcessor, len: usize) -> AudioFrame {
+        let mono = self.next_mono_chunk(len);
+        let mut dsp = self.dsp.lock().unwrap();
+
+        let position = self.position();
+        let distance = (position.x * position.x + position.y * position.y + position.z * position.z)
+            .sqrt();
+        let distance_gain = distance_gain(distance);
+        dsp.filter.update_bands(air_absorption_bands(distance));
+
+        let mut filtered = vec![0.0; mono.len()];
+        dsp.filter.process_buffer(&mono, &mut filtered);
+
+        let mut output = vec![(0.0f32, 0.0f32); mono.len()];
+        let context = hrtf::HrtfContext {
+            source: &filtered,
+            output: &mut output,
+            new_sample_vector: position,
+            prev_sample_vector: dsp.prev_sample_vector,
+            prev_left_samples: &mut dsp.prev_left_samples,
+            prev_right_samples: &mut dsp.prev_right_samples,
+            new_distance_gain: distance_gain,
+            prev_distance_gain: dsp.prev_distance_gain,
+        };
+        processor.process_samples(context);
+        dsp.prev_sample_vector = position;
+        dsp.prev_distance_gain = distance_gain;
+
+        output.iter().flat_map(|&(l, r)| [l, r]).collect()
+    }
+}
+
+/// Mixes any number of `AudioSource`s into a single stereo stream. Sources
+/// live in a generational arena so they can be registered and looked up by a
+/// stable [`SoundHandle`] independent of how many others are currently in the
+/// scene; once registered, a source stays in the mixer. Each tick, every playing source produces its next
+/// frame and the mixer sums them in place.
+pub struct AudioMixer {
+    processor: Mutex<hrtf::HrtfProcessor>,
+    sources: Mutex<Arena<Arc<AudioSource>>>,
+    chunk_len: usize,
+}
+
+impl AudioMixer {
+    pub fn new(processor: hrtf::HrtfProcessor, chunk_len: usize) -> Self {
+        Self {
+            processor: Mutex::new(processor),
+            sources: Mutex::new(Arena::new()),
+            chunk_len,
+        }
+    }
+
+    /// Registers a source with the mixer and returns a handle to it.
+    pub(crate) fn add_source(&self, source: Arc<AudioSource>) -> SoundHandle {
+        SoundHandle(self.sources.lock().unwrap().insert(source))
+    }
+
+    pub(crate) fn get_source(&self, handle: SoundHandle) -> Option<Arc<AudioSource>> {
+        self.sources.lock().unwrap().get(handle.0).cloned()
+    }
+
+    /// Advances every playing source by one chunk and returns the summed
+    /// stereo buffer (interleaved, `2 * chunk_len` samples).
+    pub fn mix_chunk(&self) -> AudioFrame {
+        let sources: Vec<Arc<AudioSource>> = self
+            .sources
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|(_, source)| source.clone())
+            .collect();
+        let mut processor = self.processor.lock().unwrap();
+
+        let mut mixed = vec![0.0f32; self.chunk_len * 2];
+        for source in &sources {
+            if !source.is_playing() {
+                continue;
+            }
+            let frame = source.produce_chunk(&mut processor, self.chunk_len);
+            for (out, sample) in mixed.iter_mut().zip(frame.iter()) {
+                *out += sample;
+            }
+        }
+        mixed
+    }
+}
@@ -0,0 +1,4 @@
+pub mod backend;
+pub mod clock;
+pub mod decode;
+pub mod mixer;
@@ -1,15 +1,18 @@
 use anyhow::Result;
+use audio::backend::AudioBackend;
+use audio::clock::{Clock, ClockedQueue};
+use audio::mixer::{AudioFrame, AudioMixer, SoundHandle};
 use cpal::traits::{DeviceTrait, HostTrait, StreamTrait};
-use filter::AudioBandProcessor;
 use macroquad::models::draw_cube;
 use macroquad::prelude::*;
-use std::sync::atomic::{AtomicBool, Ordering};
-use std::sync::mpsc::{self, Receiver, Sender};
-use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::mpsc::{self, SyncSender};
+use std::sync::{Arc, OnceLock};
 use std::thread;
 use tokio::runtime::Runtime;
 use tokio::sync::watch;
 
+mod audio;
 mod filter;
 
 const MOVE_SPEED: f32 = 5.0;
@@ -17,12 +20,146 @@ const LOOK_SPEED: f32 = 0.1;
 const INTERPOLATION_STEPS: usize = 8;
 const BLOCK_LEN: usize = 128;
 const CHUNK: usize = INTERPOLATION_STEPS * BLOCK_LEN;
+const RESAMPLE_MODE: InterpolationMode = InterpolationMode::Cubic;
+/// How many produced-but-unplayed chunks the mixing thread is allowed to get
+/// ahead of the output callback before it blocks.
+const MAX_QUEUED_CHUNKS: usize = 4;
+
+/// Interpolation strategy used when resampling decoded source audio to the device sample rate.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum InterpolationMode {
+    /// Picks the closest source sample. Cheapest, aliases the most.
+    Nearest,
+    /// Straight-line interpolation between the two nearest samples.
+    Linear,
+    /// Cosine-weighted blend between the two nearest samples; smoother than linear.
+    Cosine,
+    /// Catmull-Rom cubic interpolation over four neighboring samples.
+    Cubic,
+}
+
+/// Resamples `samples` from `src_rate` to `dst_rate` using the given interpolation mode.
+fn resample(samples: &[f32], src_rate: f32, dst_rate: f32, mode: InterpolationMode) -> Vec<f32> {
+    let ratio = dst_rate / src_rate;
+    let resampled_length = ((samples.len() as f32) * ratio) as usize;
+    let mut resampled = Vec::with_capacity(resampled_length);
+
+    for i in 0..resampled_length {
+        let src_index = i as f32 / ratio;
+        let index_floor = src_index.floor() as usize;
+        let t = src_index - index_floor as f32;
+        let last = samples.len() - 1;
+
+        let sample = match mode {
+            InterpolationMode::Nearest => samples[(src_index.round() as usize).min(last)],
+            InterpolationMode::Linear => {
+                let p1 = samples[index_floor];
+                let p2 = samples[(index_floor + 1).min(last)];
+                p1 * (1.0 - t) + p2 * t
+            }
+            InterpolationMode::Cosine => {
+                let p1 = samples[index_floor];
+                let p2 = samples[(index_floor + 1).min(last)];
+                let mu2 = (1.0 - (t * std::f32::consts::PI).cos()) / 2.0;
+                p1 * (1.0 - mu2) + p2 * mu2
+            }
+            InterpolationMode::Cubic => {
+                let i0 = index_floor.saturating_sub(1);
+                let i1 = index_floor;
+                let i2 = (index_floor + 1).min(last);
+                let i3 = (index_floor + 2).min(last);
+                let p0 = samples[i0];
+                let p1 = samples[i1];
+                let p2 = samples[i2];
+                let p3 = samples[i3];
+                p1 + 0.5
+                    * t
+                    * ((p2 - p0)
+                        + t * (2.0 * p0 - 5.0 * p1 + 4.0 * p2 - p3
+                            + t * (3.0 * (p1 - p2) + p3 - p0)))
+            }
+        };
+        resampled.push(sample);
+    }
+
+    resampled
+}
 
 struct AudioState {
-    input_tx: Sender<Vec<f32>>,
+    mixer: Arc<AudioMixer>,
+    /// Set once its asset has finished decoding on a background thread (see
+    /// [`spawn_sound_load`]); `None` until then, so the main loop just skips
+    /// positioning a source that isn't ready yet instead of blocking on it.
+    cube_sound: Arc<OnceLock<SoundHandle>>,
+    orbit_sound: Arc<OnceLock<SoundHandle>>,
     running: Arc<AtomicBool>,
 }
 
+/// Decodes and resamples `path` on a background thread, then registers and
+/// plays it on `mixer` once ready. Keeps large assets from blocking
+/// `setup_audio` (and so `stream.play()`) the way decoding them inline would.
+fn spawn_sound_load(
+    mixer: Arc<AudioMixer>,
+    path: &'static str,
+    device_sample_rate: f32,
+) -> Arc<OnceLock<SoundHandle>> {
+    let handle_slot = Arc::new(OnceLock::new());
+    let handle_slot_producer = handle_slot.clone();
+    thread::spawn(move || match audio::decode::load_mono_samples(path) {
+        Ok((samples, wav_sample_rate)) => {
+            let resampled = resample(
+                &samples,
+                wav_sample_rate as f32,
+                device_sample_rate,
+                RESAMPLE_MODE,
+            );
+            let handle = mixer.register_sound(&resampled);
+            mixer.play_sound(handle);
+            let _ = handle_slot_producer.set(handle);
+        }
+        Err(e) => eprintln!("Failed to load sound {:?}: {:?}", path, e),
+    });
+    handle_slot
+}
+
+/// Like [`spawn_sound_load`], but registers `intro_path` followed by a
+/// seamless loop of `loop_path` via [`AudioBackend::register_looping_sound`]
+/// — for background ambience with a distinct wind-up and a repeating body.
+fn spawn_looping_sound_load(
+    mixer: Arc<AudioMixer>,
+    intro_path: &'static str,
+    loop_path: &'static str,
+    device_sample_rate: f32,
+) -> Arc<OnceLock<SoundHandle>> {
+    let handle_slot = Arc::new(OnceLock::new());
+    let handle_slot_producer = handle_slot.clone();
+    thread::spawn(move || {
+        let intro = audio::decode::load_mono_samples(intro_path);
+        let loop_segment = audio::decode::load_mono_samples(loop_path);
+        match (intro, loop_segment) {
+            (Ok((intro, intro_rate)), Ok((loop_segment, loop_rate))) => {
+                let intro = resample(&intro, intro_rate as f32, device_sample_rate, RESAMPLE_MODE);
+                let loop_segment = resample(
+                    &loop_segment,
+                    loop_rate as f32,
+                    device_sample_rate,
+                    RESAMPLE_MODE,
+                );
+                let handle = mixer.register_looping_sound(&intro, &loop_segment);
+                mixer.play_sound(handle);
+                let _ = handle_slot_producer.set(handle);
+            }
+            (Err(e), _) | (_, Err(e)) => {
+                eprintln!(
+                    "Failed to load looping sound {:?}/{:?}: {:?}",
+                    intro_path, loop_path, e
+                );
+            }
+        }
+    });
+    handle_slot
+}
+
 fn conf() -> Conf {
     Conf {
         window_title: String::from("3D Audio Demo"),
@@ -39,56 +176,75 @@ fn setup_audio() -> Result<(AudioState, cpal::Stream)> {
     let config = device.default_output_config()?;
     let sample_rate = config.sample_rate().0 as f32;
 
-    // Load audio file
-    let samples = hound::WavReader::open("assets/sample-0.wav")?;
-    let wav_sample_rate = samples.spec().sample_rate as f32;
-    let samples: Vec<f32> = match samples.spec().sample_format {
-        hound::SampleFormat::Float => samples.into_samples().filter_map(Result::ok).collect(),
-        hound::SampleFormat::Int => samples
-            .into_samples::<i16>()
-            .filter_map(Result::ok)
-            .map(|s| s as f32 / i16::MAX as f32)
-            .collect(),
-    };
-
-    // Resample audio
-    let resample_ratio = sample_rate / wav_sample_rate;
-    let resampled_length = ((samples.len() as f32) * resample_ratio) as usize;
-    let mut resampled_samples = Vec::with_capacity(resampled_length);
-    for i in 0..resampled_length {
-        let src_index = i as f32 / resample_ratio;
-        let index_floor = src_index.floor() as usize;
-        let index_ceil = (index_floor + 1).min(samples.len() - 1);
-        let weight = src_index - index_floor as f32;
-        let sample = samples[index_floor] * (1.0 - weight) + samples[index_ceil] * weight;
-        resampled_samples.push(sample);
-    }
-
     // Initialize HRTF
     let hrtf_sphere = hrtf::HrirSphere::from_file("assets/hrir-3.bin", sample_rate as u32)
         .map_err(|e| anyhow::anyhow!("Failed to load HRTF: {:?}", e))?;
     let processor = hrtf::HrtfProcessor::new(hrtf_sphere, INTERPOLATION_STEPS, BLOCK_LEN);
 
-    let (input_tx, input_rx) = mpsc::channel();
-    let (output_tx, output_rx) = mpsc::channel();
+    // Chunks flow from the mixing thread to the output callback through a
+    // clock-tagged queue instead of a plain channel, so the callback can line
+    // them up against the device's own playback clock rather than trusting
+    // the producer's wall-clock pacing.
+    let output_queue = Arc::new(ClockedQueue::<AudioFrame>::new());
+    let sample_clock = Arc::new(AtomicU64::new(0));
+    // Clocks count per-channel frames, while the output buffer is interleaved.
+    let channels = config.channels().max(1) as u64;
+    // A small bounded "permit" channel throttles the mixing thread: it must
+    // acquire a permit before producing a chunk, and the output callback
+    // frees one each time it consumes a chunk. This replaces the old
+    // sleep-based pacing with a backpressure wait that can't drift.
+    let (permit_tx, permit_rx) = mpsc::sync_channel::<()>(MAX_QUEUED_CHUNKS);
     let running = Arc::new(AtomicBool::new(true));
     let running_clone = running.clone();
 
+    // One mixer for the whole scene; each emitter (the center cube, the
+    // orbiting cube, and a looping ambience bed) is registered as its own
+    // sound and driven by handle, so sounds can be added, played and stopped
+    // independently of each other and of `setup_audio`. Every asset decodes
+    // on its own background thread so none of them blocks `setup_audio` (or
+    // each other) on the way to `stream.play()`.
+    let mixer = Arc::new(AudioMixer::new(processor, CHUNK));
+    let cube_sound = spawn_sound_load(mixer.clone(), "assets/sample-0.wav", sample_rate);
+    let orbit_sound = spawn_sound_load(mixer.clone(), "assets/sample-1.wav", sample_rate);
+    // Ambience isn't positioned per-frame like the cubes above, nor does
+    // anything need to stop/resume it by handle later, so its handle is
+    // dropped once playback starts: it's meant to feel omnipresent, and just
+    // keeps the default forward-facing position `AudioSource` starts with.
+    let _ambience_sound = spawn_looping_sound_load(
+        mixer.clone(),
+        "assets/ambience-intro.wav",
+        "assets/ambience-loop.wav",
+        sample_rate,
+    );
+    let mixer_clone = mixer.clone();
+    let output_queue_producer = output_queue.clone();
+
     thread::spawn(move || {
         process_audio_chunks(
-            processor,
-            input_rx,
-            output_tx,
-            sample_rate,
+            mixer_clone,
+            output_queue_producer,
+            sample_clock,
+            permit_tx,
             running_clone,
-            resampled_samples,
         );
     });
 
     let mut leftover: Vec<f32> = Vec::new();
+    let mut stream_start: Option<cpal::StreamInstant> = None;
     let stream = device.build_output_stream(
         &config.into(),
-        move |data: &mut [f32], _: &cpal::OutputCallbackInfo| {
+        move |data: &mut [f32], info: &cpal::OutputCallbackInfo| {
+            let callback_instant = info.timestamp().playback;
+            let start_instant = *stream_start.get_or_insert(callback_instant);
+            let elapsed = callback_instant
+                .duration_since(&start_instant)
+                .unwrap_or_default();
+            // The clock due "right now": as idx advances through this single
+            // callback's buffer, samples further into it become due later,
+            // so each chunk must be checked against the clock at its own
+            // offset rather than the callback's start-of-buffer clock.
+            let callback_start_clock = Clock((elapsed.as_secs_f64() * sample_rate as f64) as u64);
+
             let mut idx = 0;
             if !leftover.is_empty() {
                 let len = leftover.len().min(data.len());
@@ -97,8 +253,18 @@ fn setup_audio() -> Result<(AudioState, cpal::Stream)> {
                 leftover.drain(..len);
             }
             while idx < data.len() {
-                match output_rx.try_recv() {
-                    Ok(buffer) => {
+                let target_clock = callback_start_clock.advance(idx as u64 / channels);
+                match output_queue.pop_next() {
+                    Some((clock, buffer)) => {
+                        if clock > target_clock {
+                            // Not due yet: hand it back and wait for the
+                            // device clock to catch up rather than playing
+                            // audio ahead of schedule.
+                            output_queue.unpop(clock, buffer);
+                            data[idx..].fill(0.0);
+                            break;
+                        }
+                        let _ = permit_rx.try_recv();
                         let len = buffer.len().min(data.len() - idx);
                         data[idx..idx + len].copy_from_slice(&buffer[..len]);
                         idx += len;
@@ -106,7 +272,7 @@ fn setup_audio() -> Result<(AudioState, cpal::Stream)> {
                             leftover.extend_from_slice(&buffer[len..]);
                         }
                     }
-                    Err(_) => {
+                    None => {
                         data[idx..].fill(0.0);
                         break;
                     }
@@ -117,76 +283,35 @@ fn setup_audio() -> Result<(AudioState, cpal::Stream)> {
         None,
     )?;
 
-    Ok((AudioState { input_tx, running }, stream))
+    Ok((
+        AudioState {
+            mixer,
+            cube_sound,
+            orbit_sound,
+            running,
+        },
+        stream,
+    ))
 }
 
 fn process_audio_chunks(
-    mut processor: hrtf::HrtfProcessor,
-    input_rx: Receiver<Vec<f32>>,
-    output_tx: Sender<Vec<f32>>,
-    sample_rate: f32,
+    mixer: Arc<AudioMixer>,
+    output_queue: Arc<ClockedQueue<AudioFrame>>,
+    sample_clock: Arc<AtomicU64>,
+    permit_tx: SyncSender<()>,
     running: Arc<AtomicBool>,
-    samples: Vec<f32>,
 ) {
-    let mut prev_left_samples = vec![];
-    let mut prev_right_samples = vec![];
-    let mut previous_sample_vector = hrtf::Vec3::new(0.0, 0.0, 1.0);
-    let mut current_sample_vector = hrtf::Vec3::new(0.0, 0.0, 1.0);
-    let prev_distance_gain = 1.0;
-    let mut filter = AudioBandProcessor::new();
-
-    // Calculate time per chunk based on sample rate
-    let chunk_duration = CHUNK as f32 / sample_rate;
-    let mut last_process_time = std::time::Instant::now();
-
     while running.load(Ordering::SeqCst) {
-        // Wait until next chunk should be processed
-        let now = std::time::Instant::now();
-        let elapsed = now.duration_since(last_process_time).as_secs_f32();
-        if elapsed < chunk_duration {
-            std::thread::sleep(std::time::Duration::from_secs_f32(chunk_duration - elapsed));
+        // Blocks until the output callback has freed a slot; this is the
+        // only pacing in the pipeline now, and it can't drift the way a
+        // fixed-duration sleep can.
+        if permit_tx.send(()).is_err() {
+            break;
         }
-        last_process_time = std::time::Instant::now();
 
-        for chunk in samples.chunks(CHUNK) {
-            if !running.load(Ordering::SeqCst) {
-                break;
-            }
-
-            let mut filter_output = vec![0.0; chunk.len()];
-            filter.process_buffer(chunk, &mut filter_output);
-
-            let mut output = vec![(0.0f32, 0.0f32); chunk.len()];
-
-            // Block waiting for position update
-            if let Ok(position_data) = input_rx.recv() {
-                current_sample_vector =
-                    hrtf::Vec3::new(position_data[0], position_data[1], position_data[2]);
-            }
-
-            let context = hrtf::HrtfContext {
-                source: &filter_output,
-                output: &mut output,
-                new_sample_vector: current_sample_vector, // Use current not previous
-                prev_sample_vector: previous_sample_vector,
-                prev_left_samples: &mut prev_left_samples,
-                prev_right_samples: &mut prev_right_samples,
-                new_distance_gain: prev_distance_gain,
-                prev_distance_gain,
-            };
-
-            processor.process_samples(context);
-            previous_sample_vector = current_sample_vector;
-
-            let stereo_buffer: Vec<f32> = output
-                .iter()
-                .flat_map(|&(left, right)| vec![left, right])
-                .collect();
-
-            if output_tx.send(stereo_buffer).is_err() {
-                break;
-            }
-        }
+        let buffer = mixer.tick();
+        let clock = Clock(sample_clock.fetch_add(CHUNK as u64, Ordering::SeqCst));
+        output_queue.push(clock, buffer);
     }
 }
 
@@ -290,14 +415,27 @@ async fn main() -> Result<()> {
         // Draw sound-emitting cube in center
         draw_cube(vec3(0.0, 0.5, 0.0), vec3(1.0, 1.0, 1.0), None, RED);
 
-        // Calculate audio parameters based on player position
-        let cube_pos = vec3(0.0, 0.5, 0.0);
-        let to_cube = cube_pos - position;
+        // Draw a second cube orbiting the center one, so two sources are
+        // actually playing and panning independently at once.
+        let orbit_angle = get_time() as f32;
+        let orbit_pos = vec3(orbit_angle.cos() * 3.0, 1.5, orbit_angle.sin() * 3.0);
+        draw_cube(orbit_pos, vec3(0.5, 0.5, 0.5), None, BLUE);
+
+        // Update each emitter's position relative to the listener, once its
+        // sound has finished loading.
+        let to_cube = vec3(0.0, 0.5, 0.0) - position;
+        if let Some(&handle) = audio_state.cube_sound.get() {
+            audio_state
+                .mixer
+                .set_source_position(handle, hrtf::Vec3::new(to_cube.x, to_cube.y, to_cube.z));
+        }
 
-        // Send spatial audio parameters as a vector
-        let _ = audio_state
-            .input_tx
-            .send(vec![to_cube.x, to_cube.y, to_cube.z]);
+        let to_orbit = orbit_pos - position;
+        if let Some(&handle) = audio_state.orbit_sound.get() {
+            audio_state
+                .mixer
+                .set_source_position(handle, hrtf::Vec3::new(to_orbit.x, to_orbit.y, to_orbit.z));
+        }
 
         // Draw UI text
         draw_text(